@@ -2,6 +2,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
 use ffi_support::{define_handle_map_deleter, ConcurrentHandleMap, FfiStr};
 use lazy_static::lazy_static;
 
@@ -15,9 +18,55 @@ lazy_static! {
 }
 define_handle_map_deleter!(PING_TYPES, glean_destroy_ping_type);
 
+/// Reads an array of exactly `reason_codes_len` null-terminated C strings into owned `String`s.
+/// The array itself is not scanned for a terminating null pointer.
+///
+/// ## Safety
+///
+/// `reason_codes` must point to an array of at least `reason_codes_len` valid,
+/// null-terminated C string pointers, as is guaranteed by the Kotlin/Swift callers.
+unsafe fn read_reason_codes(
+    reason_codes: *const *const c_char,
+    reason_codes_len: i32,
+) -> Vec<String> {
+    (0..reason_codes_len as isize)
+        .map(|i| {
+            CStr::from_ptr(*reason_codes.offset(i))
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect()
+}
+
 #[no_mangle]
-pub extern "C" fn glean_new_ping_type(ping_name: FfiStr, include_client_id: u8) -> u64 {
-    PING_TYPES.insert_with_log(|| Ok(PingType::new(ping_name.as_str(), include_client_id != 0)))
+pub extern "C" fn glean_new_ping_type(
+    ping_name: FfiStr,
+    include_client_id: u8,
+    reason_codes: *const *const c_char,
+    reason_codes_len: i32,
+) -> u64 {
+    // Safety: the reason codes array and length are provided together by the platform
+    // wrapper and are only read for the duration of this call.
+    let reason_codes = unsafe { read_reason_codes(reason_codes, reason_codes_len) };
+
+    PING_TYPES.insert_with_log(|| {
+        Ok(PingType::new(
+            ping_name.as_str(),
+            include_client_id != 0,
+            reason_codes,
+        ))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn glean_submit_ping_by_name(
+    glean_handle: u64,
+    ping_name: FfiStr,
+    reason: FfiStr,
+) -> u8 {
+    GLEAN.call_infallible(glean_handle, |glean| {
+        glean.submit_ping_by_name(ping_name.as_str(), reason.as_opt_str())
+    }) as u8
 }
 
 #[no_mangle]
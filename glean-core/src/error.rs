@@ -50,6 +50,36 @@ pub enum ErrorKind {
     /// TimeUnit conversion failed
     #[fail(display = "TimeUnit conversion from {} failed", _0)]
     TimeUnit(i32),
+
+    /// Failed to gzip-compress a ping payload
+    #[fail(display = "Failed to compress ping payload.")]
+    Compression(io::Error),
+}
+
+impl ErrorKind {
+    /// The stable, documented FFI error code for this kind of error.
+    ///
+    /// These are handed to consumers across the FFI boundary (e.g. Kotlin, Swift) through
+    /// [`ExternError`], so the numbering must stay stable across releases: never reuse or
+    /// reassign a code once it has shipped, the same way [`TimeUnit`]'s `TryFrom<i32>` pins
+    /// its ordinals.
+    ///
+    /// `0` and `-1` are reserved by `ffi_support` for `SUCCESS` and `PANIC` respectively, so
+    /// the range starts at `1`.
+    ///
+    /// [`ExternError`]: ffi_support::ExternError
+    /// [`TimeUnit`]: crate::metrics::TimeUnit
+    pub fn code(&self) -> i32 {
+        match self {
+            ErrorKind::Lifetime(_) => 1,
+            ErrorKind::Handle(_) => 2,
+            ErrorKind::IoError(_) => 3,
+            ErrorKind::Rkv(_) => 4,
+            ErrorKind::Json(_) => 5,
+            ErrorKind::TimeUnit(_) => 6,
+            ErrorKind::Compression(_) => 7,
+        }
+    }
 }
 
 /// A specialized [`Error`] type for this crate's operations.
@@ -124,7 +154,8 @@ impl From<StoreError> for Error {
 
 impl From<Error> for ExternError {
     fn from(error: Error) -> ExternError {
-        ffi_support::ExternError::new_error(ffi_support::ErrorCode::new(42), format!("{}", error))
+        let code = error.kind().code();
+        ffi_support::ExternError::new_error(ffi_support::ErrorCode::new(code), format!("{}", error))
     }
 }
 
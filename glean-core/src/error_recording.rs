@@ -12,7 +12,11 @@
 //! but are not actually used directly, since the `send_in_pings` value needs to match the pings of the metric that is erroring (plus the "metrics" ping),
 //! not some constant value that we could define in `metrics.yaml`.
 
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
 
 use crate::metrics::CounterMetric;
 use crate::metrics::MetricType;
@@ -20,6 +24,29 @@ use crate::CommonMetricData;
 use crate::Glean;
 use crate::Lifetime;
 
+/// The maximum number of times a given (metric, error type) pair is allowed to log a
+/// `log::warn!` line within a single run. The labeled counter in `glean.error` is still
+/// incremented for every occurrence past this cap, only the logging is throttled, so a
+/// misbehaving caller in a hot loop can't flood the log.
+const LOG_CAP: u32 = 10;
+
+lazy_static! {
+    /// Number of times each (metric, error type) pair has occurred so far this run, counting
+    /// occurrences past the cap as well as logged ones.
+    ///
+    /// Keyed by `(glean instance address, error counter identifier)` rather than just the
+    /// identifier: the storage itself is process-global (there's no per-`Glean` state to hang
+    /// this off in this module), so `Glean` instances alive at the same time keep separate
+    /// counts. Entries are never removed: a new `Glean` instance created at the address of a
+    /// dropped one continues that instance's counts.
+    static ref LOG_COUNTS: Mutex<HashMap<(usize, String), u32>> = Mutex::new(HashMap::new());
+}
+
+/// The address of a `Glean` instance, used to scope [`LOG_COUNTS`] per instance.
+fn glean_instance_id(glean: &Glean) -> usize {
+    glean as *const Glean as usize
+}
+
 /// The possible error types for metric recording.
 #[derive(Debug)]
 pub enum ErrorType {
@@ -27,6 +54,12 @@ pub enum ErrorType {
     InvalidValue,
     /// For when the label of a labeled metric does not match the restrictions
     InvalidLabel,
+    /// For when the metric caller is using the metric incorrectly (e.g. stopping a timer
+    /// that was never started)
+    InvalidState,
+    /// For when the value to be recorded overflows the metric-specific upper range limit
+    /// (e.g. a string or label that exceeds its max length)
+    InvalidOverflow,
 }
 
 impl ErrorType {
@@ -35,6 +68,8 @@ impl ErrorType {
         match self {
             ErrorType::InvalidValue => "invalid_value",
             ErrorType::InvalidLabel => "invalid_label",
+            ErrorType::InvalidState => "invalid_state",
+            ErrorType::InvalidOverflow => "invalid_overflow",
         }
     }
 }
@@ -78,10 +113,24 @@ pub fn record_error(
         ..Default::default()
     });
 
-    log::warn!("{}: {}", identifier, message);
+    if should_log(glean, metric.meta().identifier()) {
+        log::warn!("{}: {}", identifier, message);
+    }
     metric.add(glean, 1);
 }
 
+/// Returns whether a `log::warn!` should be emitted for the given error counter identifier on
+/// this `Glean` instance, tracking (but not capping) the occurrence count so downstream
+/// analysis of the counter value itself still sees the true total.
+fn should_log(glean: &Glean, error_counter_identifier: String) -> bool {
+    let mut counts = LOG_COUNTS.lock().unwrap();
+    let count = counts
+        .entry((glean_instance_id(glean), error_counter_identifier))
+        .or_insert(0);
+    *count += 1;
+    *count <= LOG_CAP
+}
+
 /// Get the number of recorded errors for the given metric and error type.
 ///
 /// *Notes: This is a **test-only** API, but we need to expose it to be used in integration tests.
@@ -122,6 +171,7 @@ pub fn test_get_num_recorded_errors(
 mod test {
     use super::*;
     use crate::metrics::*;
+    use std::convert::TryFrom;
 
     const GLOBAL_APPLICATION_ID: &str = "org.mozilla.glean.test.app";
     pub fn new_glean() -> (Glean, tempfile::TempDir) {
@@ -159,6 +209,20 @@ mod test {
             "Invalid label",
         );
 
+        record_error(
+            &glean,
+            string_metric.meta(),
+            ErrorType::InvalidState,
+            "Invalid state",
+        );
+
+        record_error(
+            &glean,
+            string_metric.meta(),
+            ErrorType::InvalidOverflow,
+            "Invalid overflow",
+        );
+
         for store in &["store1", "store2", "metrics"] {
             assert_eq!(
                 Ok(1),
@@ -178,6 +242,104 @@ mod test {
                     Some(store)
                 )
             );
+            assert_eq!(
+                Ok(1),
+                test_get_num_recorded_errors(
+                    &glean,
+                    string_metric.meta(),
+                    ErrorType::InvalidState,
+                    Some(store)
+                )
+            );
+            assert_eq!(
+                Ok(1),
+                test_get_num_recorded_errors(
+                    &glean,
+                    string_metric.meta(),
+                    ErrorType::InvalidOverflow,
+                    Some(store)
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn error_counter_is_not_capped_when_logging_is() {
+        let (glean, _t) = new_glean();
+
+        let string_metric = StringMetric::new(CommonMetricData {
+            name: "string_metric_capped".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::User,
+        });
+
+        for _ in 0..(LOG_CAP * 2) {
+            record_error(
+                &glean,
+                string_metric.meta(),
+                ErrorType::InvalidValue,
+                "Invalid value",
+            );
+        }
+
+        assert_eq!(
+            Ok(i32::try_from(LOG_CAP * 2).unwrap()),
+            test_get_num_recorded_errors(
+                &glean,
+                string_metric.meta(),
+                ErrorType::InvalidValue,
+                Some("store1")
+            )
+        );
+    }
+
+    #[test]
+    fn should_log_throttles_after_the_cap_but_keeps_counting() {
+        let (glean, _t) = new_glean();
+        let identifier = "test/should_log_throttle".to_string();
+
+        for i in 1..=LOG_CAP {
+            assert!(
+                should_log(&glean, identifier.clone()),
+                "expected call {} to log",
+                i
+            );
+        }
+        for i in 1..=LOG_CAP {
+            assert!(
+                !should_log(&glean, identifier.clone()),
+                "expected call {} past the cap to be throttled",
+                LOG_CAP + i
+            );
         }
+        assert_eq!(
+            Some(&(LOG_CAP * 2)),
+            LOG_COUNTS
+                .lock()
+                .unwrap()
+                .get(&(glean_instance_id(&glean), identifier))
+        );
+    }
+
+    #[test]
+    fn should_log_is_scoped_per_glean_instance() {
+        let (glean_a, _t_a) = new_glean();
+        let (glean_b, _t_b) = new_glean();
+        let identifier = "test/should_log_per_instance".to_string();
+
+        for _ in 0..LOG_CAP {
+            assert!(should_log(&glean_a, identifier.clone()));
+        }
+        assert!(
+            !should_log(&glean_a, identifier.clone()),
+            "glean_a should be throttled after hitting the cap"
+        );
+
+        assert!(
+            should_log(&glean_b, identifier),
+            "a different Glean instance must not inherit glean_a's throttle state"
+        );
     }
 }
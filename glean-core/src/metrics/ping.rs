@@ -0,0 +1,122 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::error_recording::{record_error, ErrorType};
+use crate::metrics::upload::compress_ping;
+use crate::CommonMetricData;
+use crate::Glean;
+
+/// A ping type, composed of a name and an optional set of allowed reason codes.
+#[derive(Debug, Clone)]
+pub struct PingType {
+    name: String,
+    include_client_id: bool,
+    reason_codes: Vec<String>,
+}
+
+impl PingType {
+    /// Creates a new ping type for the given name.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The name of the ping.
+    /// * `include_client_id` - Whether to include the client id in the assembled ping when
+    ///   submitted.
+    /// * `reason_codes` - The valid reason codes that can be attached to this ping, or an empty
+    ///   list if this ping doesn't support a reason.
+    pub fn new<A: Into<String>>(
+        name: A,
+        include_client_id: bool,
+        reason_codes: Vec<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            include_client_id,
+            reason_codes,
+        }
+    }
+
+    /// The name of this ping.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether this ping includes the client id.
+    pub fn include_client_id(&self) -> bool {
+        self.include_client_id
+    }
+
+    /// Validates `reason` against this ping's registered reason codes, recording an
+    /// `InvalidValue` error and falling back to no reason if it doesn't match.
+    ///
+    /// Returns the reason to write into the assembled ping's `ping_info.reason` field.
+    pub fn validate_reason(&self, glean: &Glean, reason: Option<&str>) -> Option<String> {
+        let reason = reason?;
+
+        if self.reason_codes.iter().any(|code| code == reason) {
+            Some(reason.into())
+        } else {
+            record_error(
+                glean,
+                &self.reason_metric(),
+                ErrorType::InvalidValue,
+                format!("Invalid reason code {} for ping {}", reason, self.name),
+            );
+            None
+        }
+    }
+
+    /// The synthetic metric identity under which reason validation errors for this ping are
+    /// recorded in the `glean.error` category.
+    ///
+    /// `record_error` splits its identifier on `/` and keeps only the first segment (treating
+    /// the rest as a label), so this name must not itself contain a `/` or the suffix would
+    /// silently be discarded.
+    fn reason_metric(&self) -> CommonMetricData {
+        CommonMetricData {
+            name: self.name.clone(),
+            category: "glean.ping".into(),
+            send_in_pings: vec![self.name.clone()],
+            ..Default::default()
+        }
+    }
+
+    /// Validates `reason`, then assembles the full ping through `Glean`'s normal collection
+    /// path (which writes the validated reason into the assembled `ping_info.reason`),
+    /// gzip-compresses the serialized result (falling back to the uncompressed body if that
+    /// fails) and hands the payload off to storage.
+    ///
+    /// Returns whether the ping was submitted; `false` if the ping isn't registered or there
+    /// was nothing to collect.
+    pub(crate) fn submit(&self, glean: &Glean, reason: Option<&str>) -> bool {
+        let reason = self.validate_reason(glean, reason);
+
+        match glean.collect(self, reason.as_deref()) {
+            Some(serialized_ping) => {
+                let payload = compress_ping(&serialized_ping);
+                glean.collect_and_store_ping(self, payload)
+            }
+            None => false,
+        }
+    }
+}
+
+impl Glean {
+    /// Submits the named ping with an optional reason code.
+    ///
+    /// The reason is validated against the ping's registered reason codes (recording an
+    /// `InvalidValue` error and falling back to no reason if it doesn't match) before being
+    /// written into the assembled ping's `ping_info.reason` field.
+    ///
+    /// Returns `false` if no ping with that name is registered.
+    pub fn submit_ping_by_name(&self, ping_name: &str, reason: Option<&str>) -> bool {
+        match self.get_ping_by_name(ping_name) {
+            Some(ping) => ping.submit(self, reason),
+            None => {
+                log::error!("Attempted to submit unknown ping '{}'", ping_name);
+                false
+            }
+        }
+    }
+}
@@ -2,6 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use chrono::{DateTime, TimeZone, Timelike};
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 
@@ -42,6 +43,75 @@ impl TimeUnit {
             Day => "%Y-%m-%d%:z",
         }
     }
+
+    /// The number of nanoseconds in a single unit of this resolution.
+    fn nanos_per_unit(self) -> u64 {
+        use TimeUnit::*;
+        match self {
+            Nanosecond => 1,
+            Microsecond => 1_000,
+            Millisecond => 1_000_000,
+            Second => 1_000_000_000,
+            Minute => 60_000_000_000,
+            Hour => 3_600_000_000_000,
+            Day => 86_400_000_000_000,
+        }
+    }
+
+    /// Truncates a duration, given in nanoseconds, to this unit's resolution.
+    ///
+    /// For example, truncating `90_000_000_000` nanoseconds (90 seconds) to [`TimeUnit::Minute`]
+    /// yields `1`, the number of whole minutes it represents.
+    pub fn truncate(self, duration_nanos: u64) -> u64 {
+        duration_nanos / self.nanos_per_unit()
+    }
+
+    /// The inverse of [`truncate`](TimeUnit::truncate): converts a value expressed in this
+    /// unit's resolution back to nanoseconds.
+    pub fn as_nanos(self, value: u64) -> u64 {
+        value * self.nanos_per_unit()
+    }
+
+    /// Truncates a [`chrono::DateTime`] down to this unit's resolution, zeroing out any
+    /// lower-resolution fields so formatting (e.g. via [`format_pattern`](TimeUnit::format_pattern))
+    /// and numeric truncation agree on the same value.
+    ///
+    /// Truncation uses the `*_opt` constructors, since a naive `and_hms`/`and_hms_nano` call
+    /// panics on a nonexistent or ambiguous local time (e.g. a DST spring-forward midnight) in
+    /// a caller-supplied timezone. If truncation isn't representable, the original,
+    /// untruncated `time` is returned rather than panicking.
+    pub fn truncate_time<Tz: TimeZone>(self, time: DateTime<Tz>) -> DateTime<Tz> {
+        use TimeUnit::*;
+        let date = time.date();
+        let fallback = time.clone();
+        match self {
+            Nanosecond => time,
+            Microsecond => date
+                .and_hms_nano_opt(
+                    time.hour(),
+                    time.minute(),
+                    time.second(),
+                    (time.nanosecond() / 1_000) * 1_000,
+                )
+                .unwrap_or(fallback),
+            Millisecond => date
+                .and_hms_nano_opt(
+                    time.hour(),
+                    time.minute(),
+                    time.second(),
+                    (time.nanosecond() / 1_000_000) * 1_000_000,
+                )
+                .unwrap_or(fallback),
+            Second => date
+                .and_hms_opt(time.hour(), time.minute(), time.second())
+                .unwrap_or(fallback),
+            Minute => date
+                .and_hms_opt(time.hour(), time.minute(), 0)
+                .unwrap_or(fallback),
+            Hour => date.and_hms_opt(time.hour(), 0, 0).unwrap_or(fallback),
+            Day => date.and_hms_opt(0, 0, 0).unwrap_or(fallback),
+        }
+    }
 }
 
 /// Trait implementation for converting an integer value
@@ -64,3 +134,47 @@ impl TryFrom<i32> for TimeUnit {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn truncate_and_as_nanos_roundtrip_on_unit_boundaries() {
+        let duration_nanos = 90_000_000_000; // 90 seconds
+        assert_eq!(1, TimeUnit::Minute.truncate(duration_nanos));
+        assert_eq!(60_000_000_000, TimeUnit::Minute.as_nanos(1));
+
+        assert_eq!(90, TimeUnit::Second.truncate(duration_nanos));
+        assert_eq!(duration_nanos, TimeUnit::Second.as_nanos(90));
+
+        assert_eq!(0, TimeUnit::Hour.truncate(duration_nanos));
+    }
+
+    #[test]
+    fn truncate_time_zeroes_out_lower_resolution_fields() {
+        let offset = FixedOffset::east(0);
+        let time = offset
+            .ymd(2020, 6, 15)
+            .and_hms_nano(13, 47, 32, 123_456_789);
+
+        assert_eq!(
+            offset.ymd(2020, 6, 15).and_hms(13, 47, 32),
+            TimeUnit::Second.truncate_time(time)
+        );
+        assert_eq!(
+            offset.ymd(2020, 6, 15).and_hms(13, 47, 0),
+            TimeUnit::Minute.truncate_time(time)
+        );
+        assert_eq!(
+            offset.ymd(2020, 6, 15).and_hms(13, 0, 0),
+            TimeUnit::Hour.truncate_time(time)
+        );
+        assert_eq!(
+            offset.ymd(2020, 6, 15).and_hms(0, 0, 0),
+            TimeUnit::Day.truncate_time(time)
+        );
+        assert_eq!(time, TimeUnit::Nanosecond.truncate_time(time));
+    }
+}
@@ -0,0 +1,122 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! # Ping payload compression
+//!
+//! Serialized ping bodies are gzip-compressed before being handed to the platform uploader,
+//! to save bandwidth on metered mobile connections. Compression failures are not fatal: the
+//! uncompressed payload is used instead and the [`ErrorKind::Compression`] error is logged.
+
+use std::io::Write;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::error::ErrorKind;
+
+/// The `Content-Encoding` header value to send when a payload was gzip-compressed.
+pub const GZIP_CONTENT_ENCODING: &str = "gzip";
+
+/// A ping payload, ready to be handed to the platform uploader, along with whether it was
+/// successfully gzip-compressed.
+pub struct PingPayload {
+    /// The payload bytes: gzip-compressed if `was_compressed` is `true`, the original
+    /// uncompressed JSON otherwise.
+    pub body: Vec<u8>,
+    /// Whether `body` is gzip-compressed. The platform uploader should set
+    /// `Content-Encoding: gzip` when this is `true`.
+    pub was_compressed: bool,
+}
+
+impl PingPayload {
+    /// The `Content-Encoding` header value to send alongside `body`, if any. Whoever
+    /// persists or uploads this payload should attach this header when it is `Some`.
+    pub fn content_encoding(&self) -> Option<&'static str> {
+        if self.was_compressed {
+            Some(GZIP_CONTENT_ENCODING)
+        } else {
+            None
+        }
+    }
+}
+
+/// Gzip-compresses a serialized ping body, falling back to the uncompressed payload if
+/// compression fails for any reason.
+///
+/// Takes the serialized ping JSON as `&str`; any string is accepted and compressed as is.
+pub fn compress_ping(payload: &str) -> PingPayload {
+    let payload = payload.as_bytes();
+    match gzip_compress(payload) {
+        Ok(body) => PingPayload {
+            body,
+            was_compressed: true,
+        },
+        Err(e) => {
+            log::warn!(
+                "Failed to gzip-compress ping payload, sending uncompressed: {}",
+                e
+            );
+            PingPayload {
+                body: payload.to_vec(),
+                was_compressed: false,
+            }
+        }
+    }
+}
+
+fn gzip_compress(payload: &[u8]) -> crate::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(payload)
+        .map_err(ErrorKind::Compression)?;
+    encoder.finish().map_err(|e| ErrorKind::Compression(e).into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn compressed_payload_round_trips_to_the_original_json() {
+        let json = r#"{"ping_info":{"seq":1},"client_info":{"client_id":"abc"}}"#;
+
+        let payload = compress_ping(json);
+        assert!(payload.was_compressed);
+        assert_ne!(json.as_bytes().to_vec(), payload.body);
+
+        let mut decoder = GzDecoder::new(&payload.body[..]);
+        let mut decoded = Vec::new();
+        decoder.read_to_end(&mut decoded).unwrap();
+
+        assert_eq!(json.as_bytes().to_vec(), decoded);
+    }
+
+    #[test]
+    fn content_encoding_is_gzip_on_success_and_unset_on_fallback() {
+        let compressed = compress_ping("{}");
+        assert_eq!(Some(GZIP_CONTENT_ENCODING), compressed.content_encoding());
+
+        let uncompressed = PingPayload {
+            body: b"{}".to_vec(),
+            was_compressed: false,
+        };
+        assert_eq!(None, uncompressed.content_encoding());
+    }
+
+    #[test]
+    fn empty_payload_round_trips_too() {
+        let json = "";
+
+        let payload = compress_ping(json);
+        assert!(payload.was_compressed);
+
+        let mut decoder = GzDecoder::new(&payload.body[..]);
+        let mut decoded = Vec::new();
+        decoder.read_to_end(&mut decoded).unwrap();
+
+        assert_eq!(json.as_bytes().to_vec(), decoded);
+    }
+}